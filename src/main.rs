@@ -1,33 +1,149 @@
-use std::io;
+use std::{
+    io,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use color_eyre::{eyre::eyre, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use reqwest::blocking::Client;
+use directories::ProjectDirs;
+use futures::{FutureExt, StreamExt};
+use reqwest::Client;
+use rusqlite::{params, Connection};
+use tokio::sync::mpsc;
 use tui::{
     backend::{Backend, CrosstermBackend},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text,
     widgets::{Block, List, ListItem, Paragraph},
     Frame, Terminal,
 };
 
 const BANNER: &str = r#"
- ████           █████              █████                                 
-░░███          ░░███              ░░███                                  
- ░███   ██████  ░███████   █████  ███████    ██████     ████████   █████ 
- ░███  ███░░███ ░███░░███ ███░░  ░░░███░    ███░░███   ░░███░░███ ███░░  
- ░███ ░███ ░███ ░███ ░███░░█████   ░███    ░███████     ░███ ░░░ ░░█████ 
+ ████           █████              █████
+░░███          ░░███              ░░███
+ ░███   ██████  ░███████   █████  ███████    ██████     ████████   █████
+ ░███  ███░░███ ░███░░███ ███░░  ░░░███░    ███░░███   ░░███░░███ ███░░
+ ░███ ░███ ░███ ░███ ░███░░█████   ░███    ░███████     ░███ ░░░ ░░█████
  ░███ ░███ ░███ ░███ ░███ ░░░░███  ░███ ███░███░░░      ░███      ░░░░███
- █████░░██████  ████████  ██████   ░░█████ ░░██████  ██ █████     ██████ 
-░░░░░  ░░░░░░  ░░░░░░░░  ░░░░░░     ░░░░░   ░░░░░░  ░░ ░░░░░     ░░░░░░  
-                                                                         
-                                                                         
+ █████░░██████  ████████  ██████   ░░█████ ░░██████  ██ █████     ██████
+░░░░░  ░░░░░░  ░░░░░░░░  ░░░░░░     ░░░░░   ░░░░░░  ░░ ░░░░░     ░░░░░░
+
+
 "#;
 
+const SPINNER_FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Colors used to render the UI, loaded from `colors.toml` in the platform
+/// config directory. Missing fields (or a missing file) fall back to the
+/// defaults this app shipped with before themes existed.
+struct Theme {
+    title: Color,
+    title_selected: Color,
+    score: Color,
+    url: Color,
+    help_heading: Color,
+    selected_indicator: Color,
+    comment_author: Color,
+    comment_score: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            title: Color::White,
+            title_selected: Color::Green,
+            score: Color::Yellow,
+            url: Color::Blue,
+            help_heading: Color::Blue,
+            selected_indicator: Color::Reset,
+            comment_author: Color::Cyan,
+            comment_score: Color::Yellow,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Default)]
+struct ThemeConfig {
+    title: Option<String>,
+    title_selected: Option<String>,
+    score: Option<String>,
+    url: Option<String>,
+    help_heading: Option<String>,
+    selected_indicator: Option<String>,
+    comment_author: Option<String>,
+    comment_score: Option<String>,
+}
+
+impl Theme {
+    fn load() -> Self {
+        let Some(config) = Self::read_config() else {
+            return Self::default();
+        };
+
+        let default = Self::default();
+        Self {
+            title: parse_color(config.title.as_deref()).unwrap_or(default.title),
+            title_selected: parse_color(config.title_selected.as_deref())
+                .unwrap_or(default.title_selected),
+            score: parse_color(config.score.as_deref()).unwrap_or(default.score),
+            url: parse_color(config.url.as_deref()).unwrap_or(default.url),
+            help_heading: parse_color(config.help_heading.as_deref())
+                .unwrap_or(default.help_heading),
+            selected_indicator: parse_color(config.selected_indicator.as_deref())
+                .unwrap_or(default.selected_indicator),
+            comment_author: parse_color(config.comment_author.as_deref())
+                .unwrap_or(default.comment_author),
+            comment_score: parse_color(config.comment_score.as_deref())
+                .unwrap_or(default.comment_score),
+        }
+    }
+
+    fn read_config() -> Option<ThemeConfig> {
+        let dirs = ProjectDirs::from("", "", "lobste-rs")?;
+        let path = dirs.config_dir().join("colors.toml");
+        let contents = std::fs::read_to_string(path).ok()?;
+        toml::from_str(&contents).ok()
+    }
+}
+
+/// Parses a `tui::style::Color` from either a named color (`"blue"`) or a
+/// `#rrggbb` hex string.
+fn parse_color(value: Option<&str>) -> Option<Color> {
+    let value = value?;
+
+    if let Some(hex) = value.strip_prefix('#') {
+        let r = u8::from_str_radix(hex.get(0..2)?, 16).ok()?;
+        let g = u8::from_str_radix(hex.get(2..4)?, 16).ok()?;
+        let b = u8::from_str_radix(hex.get(4..6)?, 16).ok()?;
+        return Some(Color::Rgb(r, g, b));
+    }
+
+    match value.to_ascii_lowercase().as_str() {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "gray" | "grey" => Some(Color::Gray),
+        "darkgray" | "darkgrey" => Some(Color::DarkGray),
+        "lightred" => Some(Color::LightRed),
+        "lightgreen" => Some(Color::LightGreen),
+        "lightyellow" => Some(Color::LightYellow),
+        "lightblue" => Some(Color::LightBlue),
+        "lightmagenta" => Some(Color::LightMagenta),
+        "lightcyan" => Some(Color::LightCyan),
+        "white" => Some(Color::White),
+        _ => None,
+    }
+}
+
 pub struct Story(serde_json::Value);
 
 impl Story {
@@ -49,17 +165,29 @@ impl Story {
         }
     }
 
-    fn url_span(&self) -> Option<text::Span> {
+    fn url_span(&self, theme: &Theme) -> Option<text::Span> {
         self.url().map(|url| {
             text::Span::styled(
                 url,
                 Style::default()
-                    .fg(tui::style::Color::Blue)
-                    .add_modifier(tui::style::Modifier::UNDERLINED),
+                    .fg(theme.url)
+                    .add_modifier(Modifier::UNDERLINED),
             )
         })
     }
 
+    fn short_id(&self) -> Option<&str> {
+        let serde_json::Value::Object(ref story) = self.0 else {
+            return None;
+        };
+
+        let serde_json::Value::String(short_id) = story.get("short_id")? else {
+            return None;
+        };
+
+        Some(short_id)
+    }
+
     fn title(&self) -> Option<&str> {
         let serde_json::Value::Object(ref story) = self.0 else {
             return None;
@@ -72,16 +200,16 @@ impl Story {
         Some(title)
     }
 
-    fn title_span(&self, selected: bool) -> Option<text::Span> {
+    fn title_span(&self, selected: bool, theme: &Theme) -> Option<text::Span> {
         self.title().map(|title| {
             text::Span::styled(
                 title,
                 Style::default()
                     .add_modifier(Modifier::BOLD)
                     .fg(if selected {
-                        tui::style::Color::Green
+                        theme.title_selected
                     } else {
-                        tui::style::Color::White
+                        theme.title
                     }),
             )
         })
@@ -99,12 +227,9 @@ impl Story {
         score.as_i64()
     }
 
-    fn score_span(&self) -> Option<text::Span> {
+    fn score_span(&self, theme: &Theme) -> Option<text::Span> {
         self.score().map(|score| {
-            text::Span::styled(
-                format!("⧋ {: <4}", score),
-                Style::default().fg(tui::style::Color::Yellow),
-            )
+            text::Span::styled(format!("⧋ {: <4}", score), Style::default().fg(theme.score))
         })
     }
 }
@@ -119,16 +244,19 @@ impl<'a> StoryWidget<'a> {
         Self { story, selected }
     }
 
-    fn to_item(&self) -> Option<ListItem<'a>> {
-        let selected_indicator = if self.selected { "► " } else { "  " };
+    fn to_item(&self, theme: &Theme) -> Option<ListItem<'a>> {
+        let selected_indicator = text::Span::styled(
+            if self.selected { "► " } else { "  " },
+            Style::default().fg(theme.selected_indicator),
+        );
 
         let span = text::Spans::from(vec![
-            selected_indicator.into(),
-            self.story.score_span()?,
+            selected_indicator,
+            self.story.score_span(theme)?,
             " ".into(),
-            self.story.title_span(self.selected)?,
+            self.story.title_span(self.selected, theme)?,
             " ".into(),
-            self.story.url_span()?,
+            self.story.url_span(theme)?,
         ]);
 
         Some(ListItem::new(span))
@@ -143,36 +271,623 @@ fn get_stories(stories: serde_json::Value) -> Option<Vec<Story>> {
     Some(stories.into_iter().map(Story).collect())
 }
 
+/// Tags whose closing (or, for void elements, opening) form marks the end of
+/// a block-level chunk of text and should be replaced with a line break
+/// rather than swallowed, so e.g. adjacent `<p>` paragraphs don't get glued
+/// together into one word.
+const BLOCK_TAGS: &[&str] = &[
+    "p", "br", "li", "ul", "ol", "div", "blockquote", "pre", "h1", "h2", "h3", "h4", "h5", "h6",
+];
+
+/// Strips markup and decodes a handful of common HTML entities from a raw
+/// `comment` body, for when the API response has no `comment_plain` field.
+fn strip_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut in_tag = false;
+    let mut is_closing = false;
+    let mut name_done = false;
+    let mut tag_name = String::new();
+    for c in input.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                is_closing = false;
+                name_done = false;
+                tag_name.clear();
+            }
+            '/' if in_tag && tag_name.is_empty() => is_closing = true,
+            '>' if in_tag => {
+                in_tag = false;
+                let name = tag_name.to_lowercase();
+                // `<br>` has no closing tag, so treat its opening form as the
+                // line break; every other block tag breaks on its close.
+                if (is_closing || name == "br") && BLOCK_TAGS.contains(&name.as_str()) {
+                    out.push('\n');
+                }
+            }
+            _ if in_tag => {
+                if c.is_whitespace() || c == '/' {
+                    name_done = true;
+                } else if !name_done {
+                    tag_name.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+pub struct Comment(serde_json::Value);
+
+impl Comment {
+    fn author(&self) -> Option<&str> {
+        let serde_json::Value::Object(ref comment) = self.0 else {
+            return None;
+        };
+
+        let serde_json::Value::String(author) = comment.get("commenting_user")? else {
+            return None;
+        };
+
+        Some(author)
+    }
+
+    fn author_span(&self, theme: &Theme) -> Option<text::Span> {
+        self.author().map(|author| {
+            text::Span::styled(
+                author,
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .fg(theme.comment_author),
+            )
+        })
+    }
+
+    fn body(&self) -> Option<String> {
+        let serde_json::Value::Object(ref comment) = self.0 else {
+            return None;
+        };
+
+        if let Some(serde_json::Value::String(body)) = comment.get("comment_plain") {
+            return Some(body.clone());
+        }
+
+        let serde_json::Value::String(body) = comment.get("comment")? else {
+            return None;
+        };
+
+        Some(strip_html(body))
+    }
+
+    fn score(&self) -> Option<i64> {
+        let serde_json::Value::Object(ref comment) = self.0 else {
+            return None;
+        };
+
+        let serde_json::Value::Number(score) = comment.get("score")? else {
+            return None;
+        };
+
+        score.as_i64()
+    }
+
+    fn score_span(&self, theme: &Theme) -> Option<text::Span> {
+        self.score().map(|score| {
+            text::Span::styled(
+                format!("⧋ {: <4}", score),
+                Style::default().fg(theme.comment_score),
+            )
+        })
+    }
+
+    fn depth(&self) -> usize {
+        let serde_json::Value::Object(ref comment) = self.0 else {
+            return 1;
+        };
+
+        comment
+            .get("indent_level")
+            .and_then(|level| level.as_u64())
+            .unwrap_or(1) as usize
+    }
+}
+
+struct CommentWidget<'a> {
+    comment: &'a Comment,
+    selected: bool,
+}
+
+impl<'a> CommentWidget<'a> {
+    fn new(comment: &'a Comment, selected: bool) -> Self {
+        Self { comment, selected }
+    }
+
+    fn to_item(&self, theme: &Theme) -> Option<ListItem<'a>> {
+        let selected_indicator = if self.selected { "► " } else { "  " };
+        let indent = " ".repeat(self.comment.depth() * 2);
+        let body = self.comment.body()?;
+        let mut lines = body.split('\n');
+
+        let first_line = text::Spans::from(vec![
+            selected_indicator.into(),
+            indent.clone().into(),
+            self.comment.score_span(theme)?,
+            " ".into(),
+            self.comment.author_span(theme)?,
+            ": ".into(),
+            lines.next().unwrap_or_default().to_string().into(),
+        ]);
+
+        let mut spans = vec![first_line];
+        spans.extend(lines.map(|line| {
+            text::Spans::from(vec![
+                "  ".into(),
+                indent.clone().into(),
+                line.to_string().into(),
+            ])
+        }));
+
+        Some(ListItem::new(text::Text::from(spans)))
+    }
+}
+
+fn get_comments(story: serde_json::Value) -> Option<Vec<Comment>> {
+    let serde_json::Value::Object(story) = story else {
+        return None;
+    };
+
+    let serde_json::Value::Array(comments) = story.get("comments")?.clone() else {
+        return None;
+    };
+
+    Some(comments.into_iter().map(Comment).collect())
+}
+
+/// A lobste.rs story feed: the newest/hottest firehose, or a single tag.
+#[derive(Clone)]
+enum Feed {
+    Newest,
+    Hottest,
+    Tag(String),
+}
+
+impl Feed {
+    fn base_path(&self) -> String {
+        match self {
+            Feed::Newest => "newest".into(),
+            Feed::Hottest => "hottest".into(),
+            Feed::Tag(tag) => format!("t/{}", tag),
+        }
+    }
+
+    /// Builds the URL for a given page; page 1 is the feed's un-paginated form.
+    fn url(&self, page: usize) -> String {
+        if page <= 1 {
+            format!("https://lobste.rs/{}.json", self.base_path())
+        } else {
+            format!("https://lobste.rs/{}/page/{}.json", self.base_path(), page)
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Feed::Newest => "newest".into(),
+            Feed::Hottest => "hottest".into(),
+            Feed::Tag(tag) => format!("#{}", tag),
+        }
+    }
+}
+
+/// Strips a tag down to the `[a-z0-9-]` characters lobste.rs tags are made
+/// of, so it can't smuggle whitespace or path separators into a feed URL.
+fn sanitize_tag(tag: &str) -> String {
+    tag.trim()
+        .to_ascii_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect()
+}
+
+/// Whether a result came from the on-disk cache or a fresh network fetch.
+#[derive(Clone, Copy)]
+enum FetchSource {
+    Cache,
+    Network,
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// On-disk SQLite cache of raw feed pages and comment threads, so the app
+/// has something to show offline and doesn't have to wait on the network
+/// for data it already fetched recently.
+struct Cache {
+    conn: Mutex<Connection>,
+    ttl: Duration,
+}
+
+/// Default time a cached feed page or comment thread is considered fresh,
+/// used when `cache.toml` is missing or doesn't set `ttl_secs`.
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(serde::Deserialize, Default)]
+struct CacheConfig {
+    ttl_secs: Option<u64>,
+}
+
+impl Cache {
+    /// Reads the cache TTL from `cache.toml` in the platform config
+    /// directory, falling back to [`DEFAULT_CACHE_TTL`] if the file, or the
+    /// `ttl_secs` field, is missing.
+    fn resolve_ttl() -> Duration {
+        Self::read_config()
+            .and_then(|config| config.ttl_secs)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_CACHE_TTL)
+    }
+
+    fn read_config() -> Option<CacheConfig> {
+        let dirs = ProjectDirs::from("", "", "lobste-rs")?;
+        let path = dirs.config_dir().join("cache.toml");
+        let contents = std::fs::read_to_string(path).ok()?;
+        toml::from_str(&contents).ok()
+    }
+
+    fn open(ttl: Duration) -> Result<Self> {
+        let dirs = ProjectDirs::from("", "", "lobste-rs")
+            .ok_or(eyre!("couldn't resolve a data directory for the cache"))?;
+        let dir = dirs.data_dir();
+        std::fs::create_dir_all(dir)?;
+
+        let conn = Connection::open(dir.join("cache.sqlite3"))?;
+        Self::with_connection(conn, ttl)
+    }
+
+    fn with_connection(conn: Connection, ttl: Duration) -> Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS feed_pages (
+                feed TEXT NOT NULL,
+                page INTEGER NOT NULL,
+                body TEXT NOT NULL,
+                fetched_at INTEGER NOT NULL,
+                PRIMARY KEY (feed, page)
+            );
+            CREATE TABLE IF NOT EXISTS comment_threads (
+                short_id TEXT PRIMARY KEY,
+                body TEXT NOT NULL,
+                fetched_at INTEGER NOT NULL
+            );",
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            ttl,
+        })
+    }
+
+    #[cfg(test)]
+    fn open_in_memory(ttl: Duration) -> Self {
+        Self::with_connection(Connection::open_in_memory().unwrap(), ttl).unwrap()
+    }
+
+    /// Backdates a feed page's `fetched_at` by `age` for expiry tests.
+    #[cfg(test)]
+    fn age_feed_page(&self, feed: &str, page: usize, age: Duration) {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE feed_pages SET fetched_at = fetched_at - ?1 WHERE feed = ?2 AND page = ?3",
+            params![age.as_secs() as i64, feed, page as i64],
+        )
+        .unwrap();
+    }
+
+    fn get_feed_page(&self, feed: &str, page: usize) -> Option<String> {
+        let conn = self.conn.lock().unwrap();
+        let (body, fetched_at) = conn
+            .query_row(
+                "SELECT body, fetched_at FROM feed_pages WHERE feed = ?1 AND page = ?2",
+                params![feed, page as i64],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .ok()?;
+
+        (now_unix().saturating_sub(fetched_at as u64) <= self.ttl.as_secs()).then_some(body)
+    }
+
+    fn put_feed_page(&self, feed: &str, page: usize, body: &str) {
+        let conn = self.conn.lock().unwrap();
+        let _ = conn.execute(
+            "INSERT INTO feed_pages (feed, page, body, fetched_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(feed, page) DO UPDATE SET body = excluded.body, fetched_at = excluded.fetched_at",
+            params![feed, page as i64, body, now_unix() as i64],
+        );
+    }
+
+    fn get_comment_thread(&self, short_id: &str) -> Option<String> {
+        let conn = self.conn.lock().unwrap();
+        let (body, fetched_at) = conn
+            .query_row(
+                "SELECT body, fetched_at FROM comment_threads WHERE short_id = ?1",
+                params![short_id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .ok()?;
+
+        (now_unix().saturating_sub(fetched_at as u64) <= self.ttl.as_secs()).then_some(body)
+    }
+
+    fn put_comment_thread(&self, short_id: &str, body: &str) {
+        let conn = self.conn.lock().unwrap();
+        let _ = conn.execute(
+            "INSERT INTO comment_threads (short_id, body, fetched_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(short_id) DO UPDATE SET body = excluded.body, fetched_at = excluded.fetched_at",
+            params![short_id, body, now_unix() as i64],
+        );
+    }
+
+    /// A fresh feed page, unless `force_refresh` asks to bypass the cache
+    /// entirely (the `r` refresh key).
+    fn lookup_feed_page(&self, feed: &str, page: usize, force_refresh: bool) -> Option<String> {
+        if force_refresh {
+            return None;
+        }
+        self.get_feed_page(feed, page)
+    }
+
+    /// A fresh comment thread, unless `force_refresh` asks to bypass the
+    /// cache entirely (the `r` refresh key).
+    fn lookup_comment_thread(&self, short_id: &str, force_refresh: bool) -> Option<String> {
+        if force_refresh {
+            return None;
+        }
+        self.get_comment_thread(short_id)
+    }
+}
+
+async fn fetch_stories(
+    client: Client,
+    cache: Arc<Cache>,
+    feed: Feed,
+    page: usize,
+    force_refresh: bool,
+) -> Result<(Vec<Story>, FetchSource)> {
+    let base_path = feed.base_path();
+
+    let cached = {
+        let cache = cache.clone();
+        let base_path = base_path.clone();
+        tokio::task::spawn_blocking(move || cache.lookup_feed_page(&base_path, page, force_refresh))
+            .await?
+    };
+    if let Some(body) = cached {
+        if let Some(stories) = serde_json::from_str(&body).ok().and_then(get_stories) {
+            return Ok((stories, FetchSource::Cache));
+        }
+    }
+
+    let body = client.get(feed.url(page)).send().await?.text().await?;
+    let stories =
+        get_stories(serde_json::from_str(&body)?).ok_or(eyre!("couldn't find stories"))?;
+
+    tokio::task::spawn_blocking(move || cache.put_feed_page(&base_path, page, &body)).await?;
+
+    Ok((stories, FetchSource::Network))
+}
+
+async fn fetch_comments(
+    client: Client,
+    cache: Arc<Cache>,
+    short_id: String,
+    force_refresh: bool,
+) -> Result<(Vec<Comment>, FetchSource)> {
+    let cached = {
+        let cache = cache.clone();
+        let short_id = short_id.clone();
+        tokio::task::spawn_blocking(move || cache.lookup_comment_thread(&short_id, force_refresh))
+            .await?
+    };
+    if let Some(body) = cached {
+        if let Some(comments) = serde_json::from_str(&body).ok().and_then(get_comments) {
+            return Ok((comments, FetchSource::Cache));
+        }
+    }
+
+    let body = client
+        .get(format!("https://lobste.rs/s/{}.json", short_id))
+        .send()
+        .await?
+        .text()
+        .await?;
+    let comments =
+        get_comments(serde_json::from_str(&body)?).ok_or(eyre!("couldn't find comments"))?;
+
+    tokio::task::spawn_blocking(move || cache.put_comment_thread(&short_id, &body)).await?;
+
+    Ok((comments, FetchSource::Network))
+}
+
+enum Mode {
+    Stories,
+    Comments,
+    Filter,
+    TagPrompt,
+}
+
+/// Scores how well `query` fuzzy-matches `target` as a subsequence, walking
+/// `query`'s characters left to right through `target`. Consecutive matches
+/// and matches right after a word boundary score higher. Returns `None` if
+/// any query character isn't found, in order, in `target`.
+fn fuzzy_score(query: &str, target: &str) -> Option<i64> {
+    let target: Vec<char> = target.to_lowercase().chars().collect();
+    let mut score = 0i64;
+    let mut pos = 0usize;
+    let mut prev_matched = false;
+
+    for q in query.to_lowercase().chars() {
+        let matched_at = target[pos..].iter().position(|&c| c == q)?;
+        let i = pos + matched_at;
+
+        let word_boundary = i == 0 || !target[i - 1].is_alphanumeric();
+        let consecutive = prev_matched && i == pos;
+        score += if consecutive {
+            5
+        } else if word_boundary {
+            3
+        } else {
+            1
+        };
+
+        prev_matched = true;
+        pos = i + 1;
+    }
+
+    Some(score)
+}
+
+enum FetchEvent {
+    Stories {
+        result: Result<(Vec<Story>, FetchSource)>,
+        feed: Feed,
+        requested_page: usize,
+    },
+    Comments {
+        result: Result<(Vec<Comment>, FetchSource)>,
+        short_id: String,
+    },
+}
+
 struct App {
     client: Client,
+    cache: Arc<Cache>,
+    theme: Theme,
+    feed: Feed,
     stories: Vec<Story>,
+    comments: Vec<Comment>,
+    comments_short_id: Option<String>,
     terminal: Terminal<CrosstermBackend<io::Stdout>>,
     selected_story_index: usize,
+    selected_comment_index: usize,
+    mode: Mode,
+    input: String,
+    tag_input: String,
+    filtered: Vec<usize>,
     page: usize,
+    loading: bool,
+    spinner_frame: usize,
+    last_stories_source: Option<FetchSource>,
+    last_comments_source: Option<FetchSource>,
+    last_stories_error: Option<String>,
+    last_comments_error: Option<String>,
+    fetch_tx: mpsc::UnboundedSender<FetchEvent>,
+    fetch_rx: mpsc::UnboundedReceiver<FetchEvent>,
 }
 
 impl App {
-    fn init() -> Result<Self> {
+    async fn init() -> Result<Self> {
         let client = Client::builder().build()?;
-        let mut terminal = App::init_screen()?;
-
-        let stories = client
-            .get("https://lobste.rs/newest.json")
-            .send()?
-            .json()
-            .map_err(|e| {
-                reset_terminal(&mut terminal);
-                e
-            })?;
-        let stories = get_stories(stories).ok_or(eyre!("couldn't find stories"))?;
+        let theme = tokio::task::spawn_blocking(Theme::load).await?;
+        let feed = Feed::Newest;
+        let cache = Arc::new(
+            tokio::task::spawn_blocking(|| Cache::open(Cache::resolve_ttl())).await??,
+        );
+        let terminal = App::init_screen()?;
+        let (fetch_tx, fetch_rx) = mpsc::unbounded_channel();
 
-        Ok(Self {
+        let mut app = Self {
             client,
-            stories,
+            cache,
+            theme,
+            feed,
+            stories: Vec::new(),
+            comments: Vec::new(),
+            comments_short_id: None,
             terminal,
             selected_story_index: 0,
+            selected_comment_index: 0,
+            mode: Mode::Stories,
+            input: String::new(),
+            tag_input: String::new(),
+            filtered: Vec::new(),
             page: 1,
-        })
+            loading: false,
+            spinner_frame: 0,
+            last_stories_source: None,
+            last_comments_source: None,
+            last_stories_error: None,
+            last_comments_error: None,
+            fetch_tx,
+            fetch_rx,
+        };
+
+        // Show a cached front page instantly, if we have one, instead of
+        // blocking startup on the network.
+        let cached_front_page = {
+            let cache = app.cache.clone();
+            let base_path = app.feed.base_path();
+            tokio::task::spawn_blocking(move || cache.get_feed_page(&base_path, 1))
+                .await
+                .map_err(|e| {
+                    reset_terminal(&mut app.terminal);
+                    e
+                })?
+        };
+        if let Some(body) = cached_front_page {
+            if let Some(stories) = serde_json::from_str(&body).ok().and_then(get_stories) {
+                app.filtered = (0..stories.len()).collect();
+                app.stories = stories;
+                app.last_stories_source = Some(FetchSource::Cache);
+            }
+        }
+
+        if app.stories.is_empty() {
+            app.spawn_stories_fetch(app.feed.clone(), 1, false);
+        }
+
+        Ok(app)
+    }
+
+    /// Recomputes `filtered` from `input` against the current `stories`,
+    /// sorted by descending fuzzy-match score.
+    fn recompute_filter(&mut self) {
+        self.selected_story_index = 0;
+
+        if self.input.is_empty() {
+            self.filtered = (0..self.stories.len()).collect();
+            return;
+        }
+
+        let mut scored: Vec<(usize, i64)> = self
+            .stories
+            .iter()
+            .enumerate()
+            .filter_map(|(i, story)| {
+                let score = fuzzy_score(&self.input, story.title()?)?;
+                Some((i, score))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1));
+
+        self.filtered = scored.into_iter().map(|(i, _)| i).collect();
+    }
+
+    /// The currently selected story, taking the active filter into account.
+    fn selected_story(&self) -> Option<&Story> {
+        let story_index = *self.filtered.get(self.selected_story_index)?;
+        self.stories.get(story_index)
     }
 
     fn init_screen() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
@@ -181,78 +896,376 @@ impl App {
         Ok(Terminal::new(CrosstermBackend::new(io::stdout()))?)
     }
 
-    fn run(&mut self) -> Result<()> {
+    fn spawn_stories_fetch(&mut self, feed: Feed, requested_page: usize, force_refresh: bool) {
+        self.loading = true;
+        self.last_stories_source = None;
+        self.last_stories_error = None;
+        let client = self.client.clone();
+        let cache = self.cache.clone();
+        let tx = self.fetch_tx.clone();
+        tokio::spawn(async move {
+            let result =
+                fetch_stories(client, cache, feed.clone(), requested_page, force_refresh).await;
+            let _ = tx.send(FetchEvent::Stories {
+                result,
+                feed,
+                requested_page,
+            });
+        });
+    }
+
+    fn spawn_comments_fetch(&mut self, short_id: String, force_refresh: bool) {
+        self.loading = true;
+        self.last_comments_source = None;
+        self.last_comments_error = None;
+        self.comments_short_id = Some(short_id.clone());
+        let client = self.client.clone();
+        let cache = self.cache.clone();
+        let tx = self.fetch_tx.clone();
+        tokio::spawn(async move {
+            let result = fetch_comments(client, cache, short_id.clone(), force_refresh).await;
+            let _ = tx.send(FetchEvent::Comments { result, short_id });
+        });
+    }
+
+    /// Applies the outcome of a background fetch. A failed fetch (dead
+    /// network, bad response, ...) is surfaced in the help bar instead of
+    /// tearing down the whole session, so the user can keep browsing
+    /// whatever's already cached.
+    fn apply_fetch_event(&mut self, event: FetchEvent) {
+        self.loading = false;
+        match event {
+            FetchEvent::Stories {
+                result,
+                feed,
+                requested_page,
+            } => match result {
+                Ok((stories, source)) => {
+                    if stories.is_empty() && requested_page > 1 {
+                        // the feed has no more pages; stay put instead of
+                        // navigating to a blank one
+                    } else {
+                        self.feed = feed;
+                        self.stories = stories;
+                        self.page = requested_page;
+                        self.input.clear();
+                        self.recompute_filter();
+                        self.selected_story_index = 0;
+                        self.last_stories_source = Some(source);
+                    }
+                }
+                Err(e) => self.last_stories_error = Some(format!("fetch failed: {e}")),
+            },
+            FetchEvent::Comments { result, short_id } => match result {
+                // Only move into the comments pane (or overwrite what's
+                // shown there) if the user hasn't since backed out of this
+                // story's comments - otherwise a refresh that was still in
+                // flight when they pressed q/Esc would yank them back in.
+                Ok((comments, source)) if self.comments_short_id.as_deref() == Some(&short_id) => {
+                    self.comments = comments;
+                    self.selected_comment_index = 0;
+                    self.mode = Mode::Comments;
+                    self.last_comments_source = Some(source);
+                }
+                Ok(_) => {}
+                // A thread that never loaded leaves the user on the story
+                // list, so report the failure in whichever pane is shown.
+                Err(e) => {
+                    let message = format!("fetch failed: {e}");
+                    if matches!(self.mode, Mode::Comments) {
+                        self.last_comments_error = Some(message);
+                    } else {
+                        self.last_stories_error = Some(message);
+                    }
+                }
+            },
+        }
+    }
+
+    async fn run(&mut self) -> Result<()> {
+        let mut events = EventStream::new();
+        let mut spinner_tick = tokio::time::interval(Duration::from_millis(80));
+
         loop {
-            self.terminal.draw(|f| {
-                Self::draw_stories(f, &self.stories, self.selected_story_index, self.page)
+            self.terminal.draw(|f| match self.mode {
+                Mode::Stories | Mode::Filter | Mode::TagPrompt => Self::draw_stories(
+                    f,
+                    &self.stories,
+                    &self.filtered,
+                    self.selected_story_index,
+                    &self.feed,
+                    self.page,
+                    self.loading,
+                    self.spinner_frame,
+                    self.last_stories_source,
+                    self.last_stories_error.as_deref(),
+                    &self.theme,
+                    match self.mode {
+                        Mode::Filter => Some(('/', self.input.as_str())),
+                        Mode::TagPrompt => Some(('t', self.tag_input.as_str())),
+                        _ => None,
+                    },
+                ),
+                Mode::Comments => Self::draw_comments(
+                    f,
+                    &self.comments,
+                    self.selected_comment_index,
+                    self.loading,
+                    self.spinner_frame,
+                    self.last_comments_source,
+                    self.last_comments_error.as_deref(),
+                    &self.theme,
+                ),
             })?;
 
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') => {
+            futures::select! {
+                event = events.next().fuse() => {
+                    let Some(event) = event else {
                         return Ok(());
+                    };
+                    if let Event::Key(key) = event? {
+                        if !self.handle_key(key)? {
+                            return Ok(());
+                        }
+                    }
+                }
+                event = self.fetch_rx.recv().fuse() => {
+                    if let Some(event) = event {
+                        self.apply_fetch_event(event);
                     }
-                    KeyCode::Down => {
+                }
+                // Only wait on the spinner tick while something is loading,
+                // so an idle app isn't woken (and redrawn) ~12x/second for no
+                // visual benefit.
+                _ = async {
+                    if self.loading {
+                        spinner_tick.tick().await;
+                    } else {
+                        futures::future::pending::<tokio::time::Instant>().await;
+                    }
+                }.fuse() => {
+                    self.spinner_frame = (self.spinner_frame + 1) % SPINNER_FRAMES.len();
+                }
+            }
+        }
+    }
+
+    /// Handles a single key press, returning `false` when the app should quit.
+    fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
+        match self.mode {
+            Mode::Stories => match key.code {
+                KeyCode::Char('q') => {
+                    return Ok(false);
+                }
+                KeyCode::Char('/') => {
+                    self.mode = Mode::Filter;
+                }
+                KeyCode::Down => {
+                    if !self.filtered.is_empty() {
                         self.selected_story_index =
-                            if self.selected_story_index == self.stories.len() - 1 {
+                            if self.selected_story_index == self.filtered.len() - 1 {
                                 0
                             } else {
                                 self.selected_story_index + 1
                             };
                     }
-                    KeyCode::Up => {
+                }
+                KeyCode::Up => {
+                    if !self.filtered.is_empty() {
                         self.selected_story_index = if self.selected_story_index == 0 {
-                            self.stories.len() - 1
+                            self.filtered.len() - 1
                         } else {
                             self.selected_story_index - 1
                         };
                     }
-                    KeyCode::Enter => {
-                        if let Some(story) = self.stories.get(self.selected_story_index) {
-                            if story.url().and_then(|url| open::that(url).ok()).is_none() {
-                                eprintln!("Error opening url");
-                            }
+                }
+                KeyCode::Enter => {
+                    if let Some(story) = self.selected_story() {
+                        if story.url().and_then(|url| open::that(url).ok()).is_none() {
+                            eprintln!("Error opening url");
                         }
                     }
-                    KeyCode::Right => {
-                        // breaks after page 5
-                        if self.page < 5 {
-                            self.page += 1;
-                            let stories = self
-                                .client
-                                .get(format!("https://lobste.rs/newest/page/{}.json", self.page))
-                                .send()?
-                                .json()?;
-
-                            self.stories =
-                                get_stories(stories).ok_or(eyre!("couldn't find stories"))?;
-                        }
+                }
+                KeyCode::Char('c') if !self.loading => {
+                    let short_id = self
+                        .selected_story()
+                        .and_then(Story::short_id)
+                        .map(str::to_string);
+
+                    if let Some(short_id) = short_id {
+                        self.spawn_comments_fetch(short_id, false);
                     }
-                    KeyCode::Left => {
-                        if self.page > 1 {
-                            self.page -= 1;
-                            let stories = self
-                                .client
-                                .get(format!("https://lobste.rs/newest/page/{}.json", self.page))
-                                .send()?
-                                .json()?;
-
-                            self.stories =
-                                get_stories(stories).ok_or(eyre!("couldn't find stories"))?;
-                        }
+                }
+                KeyCode::Char('n') if !self.loading => {
+                    self.spawn_stories_fetch(Feed::Newest, 1, false);
+                }
+                KeyCode::Char('h') if !self.loading => {
+                    self.spawn_stories_fetch(Feed::Hottest, 1, false);
+                }
+                KeyCode::Char('t') if !self.loading => {
+                    self.tag_input.clear();
+                    self.mode = Mode::TagPrompt;
+                }
+                KeyCode::Char('r') if !self.loading => {
+                    self.spawn_stories_fetch(self.feed.clone(), self.page, true);
+                }
+                KeyCode::Right if !self.loading => {
+                    self.spawn_stories_fetch(self.feed.clone(), self.page + 1, false);
+                }
+                KeyCode::Left if !self.loading && self.page > 1 => {
+                    self.spawn_stories_fetch(self.feed.clone(), self.page - 1, false);
+                }
+                _ => {}
+            },
+            Mode::TagPrompt => match key.code {
+                KeyCode::Esc => {
+                    self.tag_input.clear();
+                    self.mode = Mode::Stories;
+                }
+                KeyCode::Enter => {
+                    let tag = sanitize_tag(&self.tag_input);
+                    if !tag.is_empty() {
+                        self.spawn_stories_fetch(Feed::Tag(tag), 1, false);
                     }
-                    _ => {}
+                    self.mode = Mode::Stories;
                 }
-            }
+                KeyCode::Backspace => {
+                    self.tag_input.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.tag_input.push(c);
+                }
+                _ => {}
+            },
+            Mode::Filter => match key.code {
+                KeyCode::Esc => {
+                    self.input.clear();
+                    self.recompute_filter();
+                    self.mode = Mode::Stories;
+                }
+                KeyCode::Enter => {
+                    self.mode = Mode::Stories;
+                }
+                KeyCode::Backspace => {
+                    self.input.pop();
+                    self.recompute_filter();
+                }
+                KeyCode::Char(c) => {
+                    self.input.push(c);
+                    self.recompute_filter();
+                }
+                KeyCode::Down => {
+                    if !self.filtered.is_empty() {
+                        self.selected_story_index =
+                            if self.selected_story_index == self.filtered.len() - 1 {
+                                0
+                            } else {
+                                self.selected_story_index + 1
+                            };
+                    }
+                }
+                KeyCode::Up => {
+                    if !self.filtered.is_empty() {
+                        self.selected_story_index = if self.selected_story_index == 0 {
+                            self.filtered.len() - 1
+                        } else {
+                            self.selected_story_index - 1
+                        };
+                    }
+                }
+                _ => {}
+            },
+            Mode::Comments => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    self.mode = Mode::Stories;
+                    self.comments_short_id = None;
+                }
+                KeyCode::Char('r') if !self.loading => {
+                    if let Some(short_id) = self.comments_short_id.clone() {
+                        self.spawn_comments_fetch(short_id, true);
+                    }
+                }
+                KeyCode::Down => {
+                    if !self.comments.is_empty() {
+                        self.selected_comment_index =
+                            if self.selected_comment_index == self.comments.len() - 1 {
+                                0
+                            } else {
+                                self.selected_comment_index + 1
+                            };
+                    }
+                }
+                KeyCode::Up => {
+                    if !self.comments.is_empty() {
+                        self.selected_comment_index = if self.selected_comment_index == 0 {
+                            self.comments.len() - 1
+                        } else {
+                            self.selected_comment_index - 1
+                        };
+                    }
+                }
+                _ => {}
+            },
+        }
+
+        Ok(true)
+    }
+
+    fn spinner_span(loading: bool, frame: usize) -> text::Span<'static> {
+        if loading {
+            text::Span::styled(
+                format!(" {} loading...", SPINNER_FRAMES[frame]),
+                Style::default().fg(Color::Magenta),
+            )
+        } else {
+            text::Span::raw("")
+        }
+    }
+
+    fn source_span(source: Option<FetchSource>) -> text::Span<'static> {
+        match source {
+            Some(FetchSource::Cache) => text::Span::styled(
+                " [cached]",
+                Style::default().fg(Color::DarkGray),
+            ),
+            Some(FetchSource::Network) => text::Span::styled(
+                " [network]",
+                Style::default().fg(Color::DarkGray),
+            ),
+            None => text::Span::raw(""),
         }
     }
 
-    fn draw_stories<B: Backend>(f: &mut Frame<B>, stories: &[Story], index: usize, page: usize) {
-        let items: Vec<ListItem> = stories
+    fn error_span(error: Option<&str>) -> text::Span<'static> {
+        match error {
+            Some(message) => text::Span::styled(
+                format!(" {message}"),
+                Style::default().fg(Color::Red),
+            ),
+            None => text::Span::raw(""),
+        }
+    }
+
+    fn draw_stories<B: Backend>(
+        f: &mut Frame<B>,
+        stories: &[Story],
+        filtered: &[usize],
+        index: usize,
+        feed: &Feed,
+        page: usize,
+        loading: bool,
+        spinner_frame: usize,
+        last_fetch_source: Option<FetchSource>,
+        last_fetch_error: Option<&str>,
+        theme: &Theme,
+        prompt: Option<(char, &str)>,
+    ) {
+        let items: Vec<ListItem> = filtered
             .iter()
             .enumerate()
-            .map(|(i, s)| StoryWidget::new(s, i == index))
-            .filter_map(|s| s.to_item())
+            .filter_map(|(i, &story_index)| {
+                StoryWidget::new(stories.get(story_index)?, i == index).to_item(theme)
+            })
             .collect();
 
         let layout = tui::layout::Layout::default()
@@ -276,21 +1289,99 @@ impl App {
         let help = Paragraph::new(vec![
             vec![
                 text::Span::styled(
-                    format!("{} stories ", stories.len()),
+                    format!("{} stories ", filtered.len()),
                     Style::default()
-                        .fg(tui::style::Color::Blue)
+                        .fg(theme.help_heading)
                         .add_modifier(Modifier::BOLD),
                 ),
-                "on page ".into(),
+                "on ".into(),
+                text::Span::styled(
+                    feed.name(),
+                    Style::default()
+                        .fg(theme.help_heading)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                " page ".into(),
                 text::Span::styled(
                     page.to_string(),
                     Style::default()
-                        .fg(tui::style::Color::Blue)
+                        .fg(theme.help_heading)
                         .add_modifier(Modifier::BOLD),
                 ),
+                Self::spinner_span(loading, spinner_frame),
+                Self::source_span(last_fetch_source),
+                Self::error_span(last_fetch_error),
             ]
             .into(),
-            "↑/↓: Navigate, Enter: Open in browser, q: Quit, ←/→: Navigate pages".into(),
+            match prompt {
+                Some((prefix, input)) => text::Spans::from(vec![
+                    text::Span::styled(
+                        prefix.to_string(),
+                        Style::default()
+                            .fg(theme.help_heading)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    input.to_string().into(),
+                    "█".into(),
+                ]),
+                None => {
+                    "↑/↓: Navigate, Enter: Open in browser, c: Comments, /: Filter, n/h: Newest/Hottest, t: Tag, r: Refresh, q: Quit, ←/→: Navigate pages"
+                        .into()
+                }
+            },
+        ]);
+        f.render_widget(help, layout[2]);
+    }
+
+    fn draw_comments<B: Backend>(
+        f: &mut Frame<B>,
+        comments: &[Comment],
+        index: usize,
+        loading: bool,
+        spinner_frame: usize,
+        last_fetch_source: Option<FetchSource>,
+        last_fetch_error: Option<&str>,
+        theme: &Theme,
+    ) {
+        let items: Vec<ListItem> = comments
+            .iter()
+            .enumerate()
+            .map(|(i, c)| CommentWidget::new(c, i == index))
+            .filter_map(|c| c.to_item(theme))
+            .collect();
+
+        let layout = tui::layout::Layout::default()
+            .constraints(
+                [
+                    tui::layout::Constraint::Percentage(30),
+                    tui::layout::Constraint::Percentage(65),
+                    tui::layout::Constraint::Percentage(5),
+                ]
+                .as_ref(),
+            )
+            .margin(1)
+            .split(f.size());
+
+        let title = Paragraph::new(text::Text::raw(BANNER));
+        f.render_widget(title, layout[0]);
+
+        let items = List::new(items).block(Block::default());
+        f.render_widget(items, layout[1]);
+
+        let help = Paragraph::new(vec![
+            vec![
+                text::Span::styled(
+                    format!("{} comments ", comments.len()),
+                    Style::default()
+                        .fg(theme.help_heading)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Self::spinner_span(loading, spinner_frame),
+                Self::source_span(last_fetch_source),
+                Self::error_span(last_fetch_error),
+            ]
+            .into(),
+            "↑/↓: Navigate, r: Refresh, q/Esc: Back to stories".into(),
         ]);
         f.render_widget(help, layout[2]);
     }
@@ -311,10 +1402,11 @@ fn reset_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) {
     println!("Goodbye!");
 }
 
-fn main() -> Result<()> {
+#[tokio::main]
+async fn main() -> Result<()> {
     color_eyre::install()?;
-    let mut app = App::init()?;
-    app.run().map_err(|e| {
+    let mut app = App::init().await?;
+    app.run().await.map_err(|e| {
         reset_terminal(&mut app.terminal);
         e
     })?;
@@ -322,3 +1414,190 @@ fn main() -> Result<()> {
     reset_terminal(&mut app.terminal);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_color_reads_named_colors() {
+        assert_eq!(parse_color(Some("blue")), Some(Color::Blue));
+        assert_eq!(parse_color(Some("DarkGray")), Some(Color::DarkGray));
+    }
+
+    #[test]
+    fn parse_color_reads_hex() {
+        assert_eq!(
+            parse_color(Some("#ff00aa")),
+            Some(Color::Rgb(0xff, 0x00, 0xaa))
+        );
+    }
+
+    #[test]
+    fn parse_color_rejects_unknown_or_malformed() {
+        assert_eq!(parse_color(Some("not-a-color")), None);
+        assert_eq!(parse_color(Some("#zzzzzz")), None);
+        assert_eq!(parse_color(Some("#fff")), None);
+        assert_eq!(parse_color(None), None);
+    }
+
+    #[test]
+    fn fuzzy_score_matches_subsequence() {
+        assert!(fuzzy_score("rls", "rust language server").is_some());
+        assert!(fuzzy_score("xyz", "rust language server").is_none());
+    }
+
+    #[test]
+    fn fuzzy_score_rewards_word_boundary_matches() {
+        let word_boundary = fuzzy_score("l", "rust language").unwrap();
+        let mid_word = fuzzy_score("l", "rust pull").unwrap();
+        assert!(word_boundary > mid_word);
+    }
+
+    #[test]
+    fn fuzzy_score_rewards_longer_matches() {
+        let short = fuzzy_score("ru", "rust language").unwrap();
+        let long = fuzzy_score("rust", "rust language").unwrap();
+        assert!(long > short);
+    }
+
+    #[test]
+    fn fuzzy_score_rewards_consecutive_over_scattered_matches() {
+        let consecutive = fuzzy_score("ru", "rust language").unwrap();
+        let scattered = fuzzy_score("rl", "rust language").unwrap();
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn feed_url_is_unpaginated_on_first_page() {
+        assert_eq!(Feed::Newest.url(1), "https://lobste.rs/newest.json");
+        assert_eq!(Feed::Hottest.url(1), "https://lobste.rs/hottest.json");
+        assert_eq!(
+            Feed::Tag("rust".into()).url(1),
+            "https://lobste.rs/t/rust.json"
+        );
+    }
+
+    #[test]
+    fn feed_url_is_paginated_past_the_first_page() {
+        assert_eq!(Feed::Newest.url(2), "https://lobste.rs/newest/page/2.json");
+        assert_eq!(
+            Feed::Tag("rust".into()).url(3),
+            "https://lobste.rs/t/rust/page/3.json"
+        );
+    }
+
+    #[test]
+    fn feed_name_formats_tags_with_a_hash() {
+        assert_eq!(Feed::Newest.name(), "newest");
+        assert_eq!(Feed::Tag("rust".into()).name(), "#rust");
+    }
+
+    #[test]
+    fn sanitize_tag_strips_whitespace_and_invalid_characters() {
+        assert_eq!(sanitize_tag("  Rust Lang/2 "), "rustlang2");
+        assert_eq!(sanitize_tag("web-dev"), "web-dev");
+    }
+
+    #[test]
+    fn strip_html_separates_adjacent_paragraphs() {
+        assert_eq!(
+            strip_html("<p>First paragraph.</p><p>Second paragraph.</p>"),
+            "First paragraph.\nSecond paragraph."
+        );
+    }
+
+    #[test]
+    fn strip_html_separates_line_breaks() {
+        assert_eq!(strip_html("line one<br>line two"), "line one\nline two");
+    }
+
+    #[test]
+    fn comment_depth_reads_indent_level_with_fallback() {
+        let comment = Comment(serde_json::json!({ "indent_level": 3 }));
+        assert_eq!(comment.depth(), 3);
+
+        let missing = Comment(serde_json::json!({}));
+        assert_eq!(missing.depth(), 1);
+    }
+
+    #[test]
+    fn comment_body_prefers_plain_text_over_html() {
+        let plain = Comment(serde_json::json!({
+            "comment": "<p>html</p>",
+            "comment_plain": "plain text",
+        }));
+        assert_eq!(plain.body().as_deref(), Some("plain text"));
+
+        let html_only = Comment(serde_json::json!({
+            "comment": "<p>First</p><p>Second</p>",
+        }));
+        assert_eq!(html_only.body().as_deref(), Some("First\nSecond"));
+    }
+
+    #[test]
+    fn comment_widget_indents_by_depth_and_splits_paragraphs() {
+        let comment = Comment(serde_json::json!({
+            "commenting_user": "alice",
+            "score": 3,
+            "indent_level": 2,
+            "comment_plain": "First line.\nSecond line.",
+        }));
+
+        let theme = Theme::default();
+        let item = CommentWidget::new(&comment, false)
+            .to_item(&theme)
+            .unwrap();
+        let expected = ListItem::new(text::Text::from(vec![
+            text::Spans::from(vec![
+                "  ".into(),
+                "    ".into(),
+                comment.score_span(&theme).unwrap(),
+                " ".into(),
+                comment.author_span(&theme).unwrap(),
+                ": ".into(),
+                "First line.".to_string().into(),
+            ]),
+            text::Spans::from(vec![
+                "  ".into(),
+                "    ".into(),
+                "Second line.".to_string().into(),
+            ]),
+        ]));
+
+        assert_eq!(item, expected);
+    }
+
+    #[test]
+    fn cache_hits_within_ttl() {
+        let cache = Cache::open_in_memory(Duration::from_secs(600));
+        cache.put_feed_page("newest", 1, "fresh body");
+        assert_eq!(cache.get_feed_page("newest", 1), Some("fresh body".into()));
+    }
+
+    #[test]
+    fn cache_misses_once_expired() {
+        let cache = Cache::open_in_memory(Duration::from_secs(600));
+        cache.put_feed_page("newest", 1, "stale body");
+        cache.age_feed_page("newest", 1, Duration::from_secs(601));
+        assert_eq!(cache.get_feed_page("newest", 1), None);
+    }
+
+    #[test]
+    fn cache_misses_for_unknown_entries() {
+        let cache = Cache::open_in_memory(Duration::from_secs(600));
+        assert_eq!(cache.get_feed_page("newest", 1), None);
+        assert_eq!(cache.get_comment_thread("abc123"), None);
+    }
+
+    #[test]
+    fn cache_lookup_bypasses_a_fresh_entry_on_force_refresh() {
+        let cache = Cache::open_in_memory(Duration::from_secs(600));
+        cache.put_feed_page("newest", 1, "fresh body");
+        assert_eq!(
+            cache.lookup_feed_page("newest", 1, false),
+            Some("fresh body".into())
+        );
+        assert_eq!(cache.lookup_feed_page("newest", 1, true), None);
+    }
+}